@@ -2,6 +2,7 @@
 Module to generate problems (TD Mk Landscapes) using passed codomain, read these problems and write them (using (de)serialization ).
 */
 
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use itertools::Itertools;
 use rand_chacha::ChaChaRng;
 use serde::{Deserialize, Serialize};
@@ -11,8 +12,9 @@ use std::{
     error::Error,
     fmt::Write as fmt_write,
     fs::File,
-    io::{BufRead, BufReader, BufWriter, Write},
+    io::{BufRead, BufReader, BufWriter, Read, Write},
     path::{Path, PathBuf},
+    str::FromStr,
 };
 
 
@@ -36,6 +38,172 @@ pub struct ProblemOpt {
     pub problem_command: ProblemCommand,
     #[structopt(short = "s", long = "seed")]
     pub seed: Option<u64>,
+    ///Transparent compression applied to generated problem files (the matching suffix is appended to the
+    /// output file name). Scoped to problem files only: codomain files are written by the codomain
+    /// writer and are left uncompressed. The codomain_file command does not use this option: it writes
+    /// to the given path as-is, compressed only when that path already ends in `.gz` or `.zst`.
+    #[structopt(long = "compress", default_value = "none")]
+    pub compress: CompressionFormat,
+    ///On-disk format used to write problem files generated by the codomain_folder, configuration_folder
+    /// and configuration_file commands; the codomain_file command always writes the text format.
+    #[structopt(long = "format", default_value = "text")]
+    pub format: ProblemFormat,
+}
+
+///On-disk encoding for a `Problem`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProblemFormat {
+    Text,
+    Ron,
+    Json,
+    Bin,
+}
+
+impl ProblemFormat {
+    ///Stable one-byte tag used to record the format of each entry in a pack archive manifest.
+    fn tag(self) -> u8 {
+        match self {
+            ProblemFormat::Text => 0,
+            ProblemFormat::Ron => 1,
+            ProblemFormat::Json => 2,
+            ProblemFormat::Bin => 3,
+        }
+    }
+
+    ///File extension (including the dot) that `from_path` recognises for this format, so a generated
+    /// file's name reflects the format it is actually written in.
+    fn extension(self) -> &'static str {
+        match self {
+            ProblemFormat::Text => ".txt",
+            ProblemFormat::Ron => ".ron",
+            ProblemFormat::Json => ".json",
+            ProblemFormat::Bin => ".bin",
+        }
+    }
+
+    ///Best-effort guess of a file's format from its extension (ignoring any compression suffix),
+    /// defaulting to text for the custom format and codomain files.
+    fn from_path(path: &Path) -> ProblemFormat {
+        let mut path = PathBuf::from(path);
+        if matches!(path.extension().and_then(|ext| ext.to_str()), Some("gz") | Some("zst")) {
+            path.set_extension("");
+        }
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ron") => ProblemFormat::Ron,
+            Some("json") => ProblemFormat::Json,
+            Some("bin") => ProblemFormat::Bin,
+            _ => ProblemFormat::Text,
+        }
+    }
+}
+
+impl FromStr for ProblemFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(ProblemFormat::Text),
+            "ron" => Ok(ProblemFormat::Ron),
+            "json" => Ok(ProblemFormat::Json),
+            "bin" => Ok(ProblemFormat::Bin),
+            other => Err(format!(
+                "unknown problem format '{}', expected one of text, ron, json, bin",
+                other
+            )),
+        }
+    }
+}
+
+///Write a problem to `path` in the requested format.
+fn write_problem_in_format(
+    problem: &Problem,
+    path: &Path,
+    format: ProblemFormat,
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        ProblemFormat::Text => write_problem_text(problem, path),
+        ProblemFormat::Ron => write_problem_ron(problem, path),
+        ProblemFormat::Json => write_problem_json(problem, path),
+        ProblemFormat::Bin => write_problem_bin(problem, path),
+    }
+}
+
+///Read a problem from `path`, interpreting its contents in the requested format.
+fn read_problem_in_format(path: &Path, format: ProblemFormat) -> Result<Problem, Box<dyn Error>> {
+    match format {
+        ProblemFormat::Text => read_problem_from_file(path),
+        ProblemFormat::Ron => read_problem_from_file_de(path),
+        ProblemFormat::Json => read_problem_from_file_json(path),
+        ProblemFormat::Bin => read_problem_from_file_bin(path),
+    }
+}
+
+///Transparent on-disk compression for problem files.
+/// The streaming (de)coder is selected from the file extension on read, and the
+/// matching suffix (`.gz` / `.zst`) is appended to generated file names on write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFormat {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl CompressionFormat {
+    ///Suffix appended to a generated file name so its extension selects the decoder on read.
+    fn suffix(self) -> &'static str {
+        match self {
+            CompressionFormat::None => "",
+            CompressionFormat::Gzip => ".gz",
+            CompressionFormat::Zstd => ".zst",
+        }
+    }
+}
+
+impl FromStr for CompressionFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(CompressionFormat::None),
+            "gzip" => Ok(CompressionFormat::Gzip),
+            "zstd" => Ok(CompressionFormat::Zstd),
+            other => Err(format!(
+                "unknown compression format '{}', expected one of none, gzip, zstd",
+                other
+            )),
+        }
+    }
+}
+
+///Open `output_problem_file_path` for writing, wrapping the `BufWriter<File>` in a
+/// streaming encoder when the path ends in `.gz` or `.zst` so the rest of the writer
+/// can stay oblivious to the compression.
+fn problem_writer(output_problem_file_path: &Path) -> Result<Box<dyn Write>, Box<dyn Error>> {
+    let file = File::create(output_problem_file_path)?;
+    let buf_writer = BufWriter::new(file);
+    let writer: Box<dyn Write> = match output_problem_file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        Some("gz") => Box::new(GzEncoder::new(buf_writer, Compression::default())),
+        Some("zst") => Box::new(zstd::stream::write::Encoder::new(buf_writer, 0)?.auto_finish()),
+        _ => Box::new(buf_writer),
+    };
+    Ok(writer)
+}
+
+///Open a problem file for reading, selecting a matching decoder from the `.gz` / `.zst`
+/// extension so the line parser and `ron::de::from_reader` operate on the decoded stream.
+fn problem_reader(problem_file_path: &Path) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+    let file = File::open(problem_file_path)?;
+    let buf_reader = BufReader::new(file);
+    let reader: Box<dyn BufRead> = match problem_file_path.extension().and_then(|ext| ext.to_str())
+    {
+        Some("gz") => Box::new(BufReader::new(GzDecoder::new(buf_reader))),
+        Some("zst") => Box::new(BufReader::new(zstd::stream::read::Decoder::new(buf_reader)?)),
+        _ => Box::new(buf_reader),
+    };
+    Ok(reader)
 }
 
 #[derive(StructOpt, Debug)]
@@ -91,18 +259,75 @@ pub enum ProblemCommand {
         #[structopt(default_value = "1", short = "n")]
         number_of_problems_to_generate: u32,
     },
+    /// Transcode a single problem file from one on-disk format to another
+    #[structopt(name = "convert")]
+    Convert {
+        ///Input problem file to read
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+        ///Output problem file to write
+        #[structopt(parse(from_os_str))]
+        output: PathBuf,
+        ///Format of the input file
+        #[structopt(long = "from")]
+        from: ProblemFormat,
+        ///Format to write the output file in
+        #[structopt(long = "to")]
+        to: ProblemFormat,
+    },
+    /// Validate a stored problem against its codomain, checking structural invariants and global optima
+    #[structopt(name = "verify")]
+    Verify {
+        ///Problem file to validate
+        #[structopt(parse(from_os_str))]
+        problem_file: PathBuf,
+        ///Codomain file belonging to the problem
+        #[structopt(parse(from_os_str))]
+        codomain_file: PathBuf,
+        ///Whether the codomain was generated by the problem generator / whether the codomain contains the codomain function on the first line
+        #[structopt(short = "g")]
+        generated: bool,
+    },
+    /// Bundle a whole codomain+problem folder hierarchy into a single archive file
+    #[structopt(name = "pack")]
+    Pack {
+        ///Folder whose contents (e.g. codomain_files and problems) are packed
+        #[structopt(parse(from_os_str))]
+        input_folder: PathBuf,
+        ///Archive file to write (a .gz/.zst suffix wraps the archive in the matching compression)
+        #[structopt(parse(from_os_str))]
+        archive_path: PathBuf,
+    },
+    /// Reconstruct a folder hierarchy previously written by the pack subcommand
+    #[structopt(name = "unpack")]
+    Unpack {
+        ///Archive file to read
+        #[structopt(parse(from_os_str))]
+        archive_path: PathBuf,
+        ///Folder to reconstruct the packed hierarchy into
+        #[structopt(parse(from_os_str))]
+        output_folder: PathBuf,
+    },
 }
 
 ///Run codomain generator from command line options (structopt)
 pub fn run_opt(problem_opt: ProblemOpt) -> Result<(), Box<dyn Error>> {
     let mut rng = get_rng(problem_opt.seed);
+    let compress = problem_opt.compress;
+    let format = problem_opt.format;
     match problem_opt.problem_command {
         ProblemCommand::CodomainFolder {
             folder_paths,
             generated,
         } => {
             for folder_path in folder_paths {
-                generate_problems_from_codomain_folder(&folder_path, generated, &mut rng)?;
+                generate_problems_from_codomain_folder(
+                    &folder_path,
+                    generated,
+                    compress,
+                    format,
+                    &mut rng,
+                )?;
             }
             Ok(())
         }
@@ -114,6 +339,8 @@ pub fn run_opt(problem_opt: ProblemOpt) -> Result<(), Box<dyn Error>> {
                 generate_codomain_and_problem_from_folder(
                     &folder_path,
                     number_of_problems_to_generate,
+                    compress,
+                    format,
                     &mut rng,
                 )?;
             }
@@ -142,12 +369,130 @@ pub fn run_opt(problem_opt: ProblemOpt) -> Result<(), Box<dyn Error>> {
                 Some(&output_codomain_folder_path),
                 Some(&output_problem_folder_path),
                 number_of_problems_to_generate,
+                compress,
+                format,
                 &mut rng
             )
         }
+        ProblemCommand::Convert {
+            input,
+            output,
+            from,
+            to,
+        } => convert_problem_file(&input, &output, from, to),
+        ProblemCommand::Verify {
+            problem_file,
+            codomain_file,
+            generated,
+        } => verify_problem(&problem_file, &codomain_file, generated),
+        ProblemCommand::Pack {
+            input_folder,
+            archive_path,
+        } => pack_folder(&input_folder, &archive_path),
+        ProblemCommand::Unpack {
+            archive_path,
+            output_folder,
+        } => unpack_archive(&archive_path, &output_folder),
     }
 }
 
+///Validate that the problem at `problem_path` is internally consistent with its codomain before
+/// it is used in experiments. We reconstruct the clique tree, check the TD Mk structural invariants,
+/// and re-evaluate every stored global optimum against the codomain, reporting the first failure so
+/// malformed hand-edited files are caught early.
+pub fn verify_problem(
+    problem_path: &Path,
+    codomain_path: &Path,
+    generated: bool,
+) -> Result<(), Box<dyn Error>> {
+    let clique_tree = read_clique_tree_from_files(problem_path, codomain_path, generated)?;
+
+    let m = clique_tree.input_parameters.m;
+    let k = clique_tree.input_parameters.k;
+    let o = clique_tree.input_parameters.o;
+    let problem_size = (m - 1) * (k - o) + k;
+
+    //Structural invariant: the number of cliques matches m
+    if clique_tree.cliques.len() != m as usize {
+        return Err(format!(
+            "expected {} cliques but found {}",
+            m,
+            clique_tree.cliques.len()
+        )
+        .into());
+    }
+
+    //Structural invariants on the cliques: size and index range.
+    // Note: we do not check that storage-adjacent cliques overlap in exactly o variables — that
+    // only holds for a path-shaped tree, whereas b is a branching factor and for b > 1 the clique
+    // tree branches, so cliques adjacent in storage order need not share o variables.
+    for (clique_index, clique) in clique_tree.cliques.iter().enumerate() {
+        if clique.len() != k as usize {
+            return Err(format!(
+                "clique {} has {} indices but k is {}",
+                clique_index,
+                clique.len(),
+                k
+            )
+            .into());
+        }
+        for &variable_index in clique {
+            if variable_index >= problem_size {
+                return Err(format!(
+                    "clique {} contains index {} outside the range 0..{}",
+                    clique_index, variable_index, problem_size
+                )
+                .into());
+            }
+        }
+    }
+
+    //Re-evaluate every stored global optimum against the codomain
+    let epsilon = 1e-9 * m as f64;
+    for (string_index, solution) in clique_tree.glob_optima_strings.iter().enumerate() {
+        if solution.len() != problem_size as usize {
+            return Err(format!(
+                "global optimum {} has length {} but problem size is {}",
+                string_index,
+                solution.len(),
+                problem_size
+            )
+            .into());
+        }
+        //Reuse the clique tree's own evaluation so the bit→codomain-index convention always matches
+        // the generator instead of being re-derived (and possibly inverted) here.
+        let score = clique_tree.calculate_fitness(solution);
+        if (score - clique_tree.glob_optima_score).abs() > epsilon {
+            return Err(format!(
+                "global optimum {} evaluates to {} but glob_optima_score is {} (epsilon {})",
+                string_index, score, clique_tree.glob_optima_score, epsilon
+            )
+            .into());
+        }
+    }
+
+    println!(
+        "{}: consistent ({} cliques, {} global optima)",
+        problem_path.display(),
+        clique_tree.cliques.len(),
+        clique_tree.glob_optima_strings.len()
+    );
+    Ok(())
+}
+
+///Read a problem from `input` in the `from` format and rewrite it to `output` in the `to` format.
+/// This lets a user generate once in the fast binary format and export to RON/JSON for inspection
+/// without regenerating from the codomain.
+pub fn convert_problem_file(
+    input: &Path,
+    output: &Path,
+    from: ProblemFormat,
+    to: ProblemFormat,
+) -> Result<(), Box<dyn Error>> {
+    let problem = read_problem_in_format(input, from)?;
+    write_problem_in_format(&problem, output, to)
+}
+
 ///Structure to store a generated problem for writing to a file
 /// The difference with the CliqueTree structure is the exclusion of the codomain values and function (as these are stored separately)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -174,6 +519,8 @@ impl Problem {
 pub fn generate_problems_from_codomain_folder(
     parent_folder_path: &Path,
     generated: bool,
+    compress: CompressionFormat,
+    format: ProblemFormat,
     rng: &mut ChaChaRng
 ) -> Result<(), Box<dyn Error>> {
     let mut codomain_folder_path = PathBuf::from(parent_folder_path);
@@ -203,13 +550,20 @@ pub fn generate_problems_from_codomain_folder(
         let clique_trees_paths = get_clique_trees_paths_from_codomain_folder(&folder, generated, rng)?;
         for (clique_tree, path_buf) in clique_trees_paths {
             let mut output_path = output_folder_path.clone();
-            output_path.push(
-                path_buf
-                    .file_name()
-                    .ok_or("could not get filename of codomain file")?,
-            );
+            //Name the problem after the codomain file's stem, with a format-appropriate extension and
+            // the compression suffix, so both its format and its (de)coder are recoverable from the name.
+            let file_stem = path_buf
+                .file_stem()
+                .ok_or("could not get filename of codomain file")?
+                .to_string_lossy();
+            output_path.push(format!(
+                "{}{}{}",
+                file_stem,
+                format.extension(),
+                compress.suffix()
+            ));
             //write the output problems to disk
-            write_problem_to_file(&clique_tree, &output_path)?;
+            write_problem_in_format(&Problem::new(&clique_tree), &output_path, format)?;
         }
     }
     Ok(())
@@ -219,6 +573,8 @@ pub fn generate_problems_from_codomain_folder(
 pub fn generate_codomain_and_problem_from_folder(
     input_folder_path: &Path,
     number_of_problems_to_generate: u32,
+    compress: CompressionFormat,
+    format: ProblemFormat,
     rng: &mut ChaChaRng
 ) -> Result<(), Box<dyn Error>> {
     //Use the input_folder_path to get the problem_generation folder and problems folder paths
@@ -234,7 +590,15 @@ pub fn generate_codomain_and_problem_from_folder(
 
     // generate all codomain and problem files and write them to the codomain_files and problems folders
     for file in file_entries {
-        generate_codomain_and_problem(&file, None, None, number_of_problems_to_generate, rng)?;
+        generate_codomain_and_problem(
+            &file,
+            None,
+            None,
+            number_of_problems_to_generate,
+            compress,
+            format,
+            rng,
+        )?;
     }
     Ok(())
 }
@@ -247,6 +611,8 @@ pub fn generate_codomain_and_problem(
     output_codomain_folder_path: Option<&Path>,
     output_problem_folder_path: Option<&Path>,
     number_of_problems_to_generate: u32,
+    compress: CompressionFormat,
+    format: ProblemFormat,
     rng: &mut ChaChaRng
 ) -> Result<(), Box<dyn Error>> {
     //Get the configuration parameters from the input configuration file
@@ -280,8 +646,8 @@ pub fn generate_codomain_and_problem(
             let mut output_problem_file_path = output_problem_folder_path_buf.clone();
             let mut output_codomain_file_path = output_codomain_folder_path_buf.clone();
 
-            let output_file_name = format!(
-                "{}_{}_{}_{}_{}_{}.txt",
+            let base_file_name = format!(
+                "{}_{}_{}_{}_{}_{}",
                 codomain_function.to_io_string(),
                 input_parameters.m,
                 input_parameters.k,
@@ -290,8 +656,18 @@ pub fn generate_codomain_and_problem(
                 num
             );
 
-            output_problem_file_path.push(output_file_name.clone());
-            output_codomain_file_path.push(output_file_name);
+            //The problem file gets a format-appropriate extension followed by the compression suffix,
+            // so both its format and its (de)coder are recoverable from the name; the codomain file is
+            // written verbatim by the codomain writer and keeps the plain text name.
+            output_problem_file_path.push(format!(
+                "{}{}{}",
+                base_file_name,
+                format.extension(),
+                compress.suffix()
+            ));
+            //Codomain files are intentionally written uncompressed: `generate_write_return` owns the
+            // codomain write path, so --compress is scoped to problem files only (see ProblemOpt::compress).
+            output_codomain_file_path.push(format!("{}.txt", base_file_name));
             //println!("constructed output file path: {:?}", output_file_path);
 
             let codomain =
@@ -306,7 +682,7 @@ pub fn generate_codomain_and_problem(
             );
 
             //Write the problem to disk
-            write_problem_to_file(&clique_tree, &output_problem_file_path)?;
+            write_problem_in_format(&Problem::new(&clique_tree), &output_problem_file_path, format)?;
         }
     }
     Ok(())
@@ -331,7 +707,10 @@ pub fn read_clique_tree_from_files(
     codomain_path: &Path,
     generated: bool,
 ) -> Result<CliqueTree, Box<dyn Error>> {
-    let problem = read_problem_from_file(problem_path)?;
+    //Pick the reader from the problem file's extension so this works for every format in the
+    // series (text/ron/json/bin), not just the custom text format.
+    let format = ProblemFormat::from_path(problem_path);
+    let problem = read_problem_in_format(problem_path, format)?;
     let skip_lines = if generated { 2 } else { 1 };
     let codomain = read_codomain(&problem.input_parameters, codomain_path, skip_lines)?;
     Ok(CliqueTree::construct_from_problem_codomain(
@@ -384,18 +763,22 @@ pub fn write_problem_to_file(
     clique_tree: &CliqueTree,
     output_problem_file_path: &Path,
 ) -> Result<(), Box<dyn Error>> {
-    let file = File::create(output_problem_file_path)?;
-    let mut buf_writer = BufWriter::new(file);
+    write_problem_text(&Problem::new(clique_tree), output_problem_file_path)
+}
+
+///Write a problem to file in the custom line-oriented text format.
+fn write_problem_text(problem: &Problem, output_problem_file_path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut buf_writer = problem_writer(output_problem_file_path)?;
     let mut write_buffer = String::new();
 
     //Write the input parameters on the first line
     writeln!(
         write_buffer,
         "{} {} {} {}",
-        clique_tree.input_parameters.m,
-        clique_tree.input_parameters.k,
-        clique_tree.input_parameters.o,
-        clique_tree.input_parameters.b
+        problem.input_parameters.m,
+        problem.input_parameters.k,
+        problem.input_parameters.o,
+        problem.input_parameters.b
     )?;
     buf_writer.write_all(write_buffer.as_bytes())?;
     write_buffer.clear();
@@ -406,17 +789,17 @@ pub fn write_problem_to_file(
     //      solutions
 
     //fitness
-    writeln!(write_buffer, "{}", clique_tree.glob_optima_score)?;
+    writeln!(write_buffer, "{}", problem.glob_optima_score)?;
     buf_writer.write_all(write_buffer.as_bytes())?;
     write_buffer.clear();
 
     //number_of_solutions
-    writeln!(write_buffer, "{}", clique_tree.glob_optima_strings.len())?;
+    writeln!(write_buffer, "{}", problem.glob_optima_strings.len())?;
     buf_writer.write_all(write_buffer.as_bytes())?;
     write_buffer.clear();
 
     //solutions
-    for sol in &clique_tree.glob_optima_strings {
+    for sol in &problem.glob_optima_strings {
         for bit in sol {
             write!(write_buffer, "{}", bit)?;
         }
@@ -427,7 +810,7 @@ pub fn write_problem_to_file(
 
     //Cliques/Subfunctions
     //      Per clique; variable indices
-    for clique in &clique_tree.cliques {
+    for clique in &problem.cliques {
         for variable_index in clique {
             write!(write_buffer, "{} ", variable_index)?;
         }
@@ -449,16 +832,18 @@ pub fn write_problem_to_file_ser(
     clique_tree: &CliqueTree,
     file_path: &Path,
 ) -> Result<(), Box<dyn Error>> {
-    let file = File::create(file_path)?;
-    let mut buf_writer = BufWriter::new(file);
-    let mut write_buffer = String::new();
+    write_problem_ron(&Problem::new(clique_tree), file_path)
+}
 
-    let problem = Problem::new(clique_tree);
+///Write a problem to file as (pretty) RON.
+fn write_problem_ron(problem: &Problem, file_path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut buf_writer = problem_writer(file_path)?;
+    let mut write_buffer = String::new();
 
     //Write problem to file
     let my_config = ron::ser::PrettyConfig::new().with_depth_limit(4);
     let string =
-        ron::ser::to_string_pretty(&problem, my_config).map_err(|_| "Serialization error!")?;
+        ron::ser::to_string_pretty(problem, my_config).map_err(|_| "Serialization error!")?;
 
     write!(write_buffer, "{}", string)?;
     buf_writer.write_all(write_buffer.as_bytes())?;
@@ -469,10 +854,25 @@ pub fn write_problem_to_file_ser(
     Ok(())
 }
 
+///Write problem to file using JSON serialization
+pub fn write_problem_to_file_json(
+    clique_tree: &CliqueTree,
+    file_path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    write_problem_json(&Problem::new(clique_tree), file_path)
+}
+
+///Write a problem to file as (pretty) JSON.
+fn write_problem_json(problem: &Problem, file_path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut buf_writer = problem_writer(file_path)?;
+    serde_json::to_writer_pretty(&mut buf_writer, problem)?;
+    buf_writer.flush()?;
+    Ok(())
+}
+
 ///Read problem from file
 pub fn read_problem_from_file(file_path: &Path) -> Result<Problem, Box<dyn Error>> {
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
+    let reader = problem_reader(file_path)?;
     let mut content_iter = reader.lines();
 
     //Read input parameters
@@ -552,8 +952,461 @@ pub fn read_problem_from_file(file_path: &Path) -> Result<Problem, Box<dyn Error
 
 ///Read problem from file using deserialization
 pub fn read_problem_from_file_de(file_path: &Path) -> Result<Problem, Box<dyn Error>> {
-    let f = File::open(file_path)?;
-    let mut reader = BufReader::new(f);
+    let mut reader = problem_reader(file_path)?;
     let problem = ron::de::from_reader(&mut reader)?;
     Ok(problem)
+}
+
+///Read problem from file using JSON deserialization
+pub fn read_problem_from_file_json(file_path: &Path) -> Result<Problem, Box<dyn Error>> {
+    let reader = problem_reader(file_path)?;
+    let problem = serde_json::from_reader(reader)?;
+    Ok(problem)
+}
+
+///Magic bytes identifying a packed problem/codomain archive.
+const ARCHIVE_MAGIC: &[u8; 4] = b"TDAR";
+///Current version of the archive manifest layout.
+const ARCHIVE_VERSION: u16 = 1;
+
+///Recursively collect all files below `current`, pushing their full paths into `out`.
+fn collect_files(current: &Path, out: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    for entry in current.read_dir()? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, out)?;
+        } else {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+///Bundle the whole folder hierarchy rooted at `input_folder` into a single archive file.
+/// The manifest lists each relative path, its (best-effort) format and byte length; the file
+/// contents follow concatenated in the same order, so `unpack_archive` can restore the exact layout.
+/// The archive itself is wrapped in gzip/zstd when `archive_path` ends in `.gz`/`.zst`.
+pub fn pack_folder(input_folder: &Path, archive_path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut files = Vec::new();
+    collect_files(input_folder, &mut files)?;
+    files.sort();
+
+    let mut writer = problem_writer(archive_path)?;
+    writer.write_all(ARCHIVE_MAGIC)?;
+    writer.write_all(&ARCHIVE_VERSION.to_le_bytes())?;
+    writer.write_all(&(files.len() as u32).to_le_bytes())?;
+
+    //First pass: write each file's manifest entry (relative path, format tag, length) using the
+    // on-disk length from its metadata, so we never hold file contents in memory here.
+    for file in &files {
+        let relative_path = file
+            .strip_prefix(input_folder)?
+            .to_string_lossy()
+            .replace('\\', "/");
+        let byte_len = std::fs::metadata(file)?.len();
+
+        writer.write_all(&(relative_path.len() as u32).to_le_bytes())?;
+        writer.write_all(relative_path.as_bytes())?;
+        writer.write_all(&[ProblemFormat::from_path(file).tag()])?;
+        writer.write_all(&byte_len.to_le_bytes())?;
+    }
+
+    //Second pass: re-read each file from disk and stream its bytes in manifest order, so peak memory
+    // stays O(largest file) rather than O(whole tree).
+    for file in &files {
+        let mut reader = BufReader::new(File::open(file)?);
+        std::io::copy(&mut reader, &mut writer)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+///Reconstruct the directory hierarchy previously written by `pack_folder` under `output_folder`.
+pub fn unpack_archive(archive_path: &Path, output_folder: &Path) -> Result<(), Box<dyn Error>> {
+    let mut reader = problem_reader(archive_path)?;
+
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != ARCHIVE_MAGIC {
+        return Err("not a packed problem archive (bad magic bytes)".into());
+    }
+
+    let version = read_u16(&mut reader)?;
+    if version != ARCHIVE_VERSION {
+        return Err(format!(
+            "unsupported archive version {} (expected {})",
+            version, ARCHIVE_VERSION
+        )
+        .into());
+    }
+
+    let number_of_entries = read_u32(&mut reader)?;
+
+    //Read the manifest: relative path, format tag (recorded, not needed for a verbatim restore), byte length.
+    //The entry count is attacker-controlled, so we cap the pre-allocation rather than trusting it.
+    let mut entries = Vec::with_capacity((number_of_entries as usize).min(MANIFEST_PREALLOC_CAP));
+    for _ in 0..number_of_entries {
+        let path_len = read_u32(&mut reader)?;
+        if path_len as usize > MAX_ARCHIVE_PATH_LEN {
+            return Err(format!(
+                "archive manifest path length {} exceeds the maximum of {} bytes",
+                path_len, MAX_ARCHIVE_PATH_LEN
+            )
+            .into());
+        }
+        let mut path_buf = vec![0u8; path_len as usize];
+        reader.read_exact(&mut path_buf)?;
+        let relative_path = String::from_utf8(path_buf)?;
+
+        let mut tag = [0u8; 1];
+        reader.read_exact(&mut tag)?;
+
+        let byte_len = read_u64(&mut reader)?;
+        entries.push((relative_path, byte_len));
+    }
+
+    //Read the concatenated contents and write each file back to its relative path
+    for (relative_path, byte_len) in entries {
+        //Reject absolute/`..`/root components and confirm the target stays under output_folder (zip-slip).
+        let output_path = sanitized_output_path(output_folder, &relative_path)?;
+
+        //Read exactly byte_len bytes, but grow the buffer as the bytes actually arrive so a bogus
+        // length in a malformed archive cannot force a huge up-front allocation.
+        let mut data = Vec::new();
+        let read = (&mut reader).take(byte_len).read_to_end(&mut data)?;
+        if read as u64 != byte_len {
+            return Err(format!(
+                "archive truncated: expected {} bytes for '{}' but only {} remain",
+                byte_len, relative_path, read
+            )
+            .into());
+        }
+
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&output_path, data)?;
+    }
+
+    Ok(())
+}
+
+///Upper bound on a manifest path length, and on how many manifest entries we pre-allocate for,
+/// so a malformed or malicious archive cannot trigger a huge allocation from an unvalidated count.
+const MAX_ARCHIVE_PATH_LEN: usize = 4096;
+const MANIFEST_PREALLOC_CAP: usize = 4096;
+
+///Join an untrusted archive-relative path onto `base`, rejecting absolute paths and any `..` or
+/// root components so a malicious manifest entry (e.g. `/etc/cron.d/x` or `../../x`) cannot write
+/// outside `base` (zip-slip).
+fn sanitized_output_path(base: &Path, relative_path: &str) -> Result<PathBuf, Box<dyn Error>> {
+    use std::path::Component;
+
+    let mut output_path = PathBuf::from(base);
+    for component in Path::new(relative_path).components() {
+        match component {
+            Component::Normal(part) => output_path.push(part),
+            Component::CurDir => {}
+            other => {
+                return Err(format!(
+                    "archive entry '{}' contains an illegal path component {:?}",
+                    relative_path, other
+                )
+                .into())
+            }
+        }
+    }
+
+    //Defense in depth: the assembled path must still live under base.
+    if !output_path.starts_with(base) {
+        return Err(format!(
+            "archive entry '{}' escapes the output folder",
+            relative_path
+        )
+        .into());
+    }
+
+    Ok(output_path)
+}
+
+///Magic bytes identifying the compact binary problem format.
+const PROBLEM_BIN_MAGIC: &[u8; 4] = b"TDMK";
+///Current version of the binary problem layout; bumped whenever the on-disk layout changes.
+const PROBLEM_BIN_VERSION: u16 = 1;
+
+///Number of bytes needed to pack `problem_size` bits (LSB-first within each byte).
+fn packed_byte_len(problem_size: u32) -> usize {
+    ((problem_size + 7) / 8) as usize
+}
+
+///Pack a global optimum bit string into bytes, bit `i` living in bit `i % 8` of byte `i / 8`.
+fn pack_bit_string(bits: &[u32], problem_size: u32) -> Vec<u8> {
+    let mut bytes = vec![0u8; packed_byte_len(problem_size)];
+    for (i, &bit) in bits.iter().enumerate() {
+        if bit != 0 {
+            bytes[i / 8] |= 1 << (i % 8);
+        }
+    }
+    bytes
+}
+
+///Unpack `problem_size` bits from `bytes` into a bit string, inverting `pack_bit_string`.
+fn unpack_bit_string(bytes: &[u8], problem_size: u32) -> Vec<u32> {
+    (0..problem_size as usize)
+        .map(|i| u32::from((bytes[i / 8] >> (i % 8)) & 1))
+        .collect()
+}
+
+fn read_u16<R: Read>(reader: &mut R) -> Result<u16, Box<dyn Error>> {
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, Box<dyn Error>> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_f64<R: Read>(reader: &mut R) -> Result<f64, Box<dyn Error>> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(f64::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, Box<dyn Error>> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+///Write the problem to file in the compact little-endian binary format.
+/// The fixed header lets `read_problem_from_file_bin` reject incompatible files by version,
+/// and packing the bit strings gives much smaller, faster-to-load banks than the text/RON paths.
+pub fn write_problem_to_file_bin(
+    clique_tree: &CliqueTree,
+    output_problem_file_path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    write_problem_bin(&Problem::new(clique_tree), output_problem_file_path)
+}
+
+///Write a problem to file in the compact little-endian binary format.
+fn write_problem_bin(problem: &Problem, output_problem_file_path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut writer = problem_writer(output_problem_file_path)?;
+    let params = &problem.input_parameters;
+
+    //Header: magic, version, then the four input parameters
+    writer.write_all(PROBLEM_BIN_MAGIC)?;
+    writer.write_all(&PROBLEM_BIN_VERSION.to_le_bytes())?;
+    for value in [params.m, params.k, params.o, params.b] {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+
+    //Global optima: score, count, then the packed bit strings
+    writer.write_all(&problem.glob_optima_score.to_le_bytes())?;
+    writer.write_all(&(problem.glob_optima_strings.len() as u32).to_le_bytes())?;
+    let problem_size = (params.m - 1) * (params.k - params.o) + params.k;
+    for sol in &problem.glob_optima_strings {
+        writer.write_all(&pack_bit_string(sol, problem_size))?;
+    }
+
+    //Cliques: the m cliques each as k u32 indices
+    for clique in &problem.cliques {
+        for variable_index in clique {
+            writer.write_all(&variable_index.to_le_bytes())?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+///Read a problem from a file in the compact binary format written by `write_problem_to_file_bin`.
+/// The magic bytes and version field are validated up front so malformed or future-layout files
+/// are rejected rather than silently misparsed.
+pub fn read_problem_from_file_bin(file_path: &Path) -> Result<Problem, Box<dyn Error>> {
+    let mut reader = problem_reader(file_path)?;
+
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != PROBLEM_BIN_MAGIC {
+        return Err("not a binary problem file (bad magic bytes)".into());
+    }
+
+    let version = read_u16(&mut reader)?;
+    if version != PROBLEM_BIN_VERSION {
+        return Err(format!(
+            "unsupported binary problem format version {} (expected {})",
+            version, PROBLEM_BIN_VERSION
+        )
+        .into());
+    }
+
+    let m = read_u32(&mut reader)?;
+    let k = read_u32(&mut reader)?;
+    let o = read_u32(&mut reader)?;
+    let b = read_u32(&mut reader)?;
+    let input_parameters = InputParameters::new_from_primitives(m, k, o, b);
+
+    let glob_optima_score = read_f64(&mut reader)?;
+    let number_of_global_optima = read_u32(&mut reader)? as usize;
+
+    let problem_size = (m - 1) * (k - o) + k;
+    let byte_len = packed_byte_len(problem_size);
+    //The optima count is file-supplied, so cap the pre-allocation rather than trusting it.
+    let mut glob_optima_strings =
+        Vec::with_capacity(number_of_global_optima.min(MANIFEST_PREALLOC_CAP));
+    for _ in 0..number_of_global_optima {
+        //Grow the buffer as the bytes arrive so a bogus count/size cannot force a huge allocation.
+        let mut buf = Vec::new();
+        let read = (&mut reader).take(byte_len as u64).read_to_end(&mut buf)?;
+        if read != byte_len {
+            return Err("binary problem file truncated while reading global optima".into());
+        }
+        glob_optima_strings.push(unpack_bit_string(&buf, problem_size));
+    }
+
+    //m and k come straight from the untrusted header, so cap the pre-allocations the same way as
+    // the optima count; the loops still read the claimed counts and fail on truncation below.
+    let mut cliques = Vec::with_capacity((m as usize).min(MANIFEST_PREALLOC_CAP));
+    for _ in 0..m as usize {
+        let mut clique_indices = Vec::with_capacity((k as usize).min(MANIFEST_PREALLOC_CAP));
+        for _ in 0..k as usize {
+            clique_indices.push(read_u32(&mut reader)?);
+        }
+        cliques.push(clique_indices);
+    }
+
+    Ok(Problem {
+        input_parameters,
+        glob_optima_score,
+        glob_optima_strings,
+        cliques,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///A small but structurally valid problem (m=2, k=3, o=1, b=1, problem_size=5).
+    fn sample_problem() -> Problem {
+        Problem {
+            input_parameters: InputParameters::new_from_primitives(2, 3, 1, 1),
+            glob_optima_score: 3.0,
+            glob_optima_strings: vec![vec![1, 0, 1, 1, 0], vec![0, 1, 0, 0, 1]],
+            cliques: vec![vec![0, 1, 2], vec![2, 3, 4]],
+        }
+    }
+
+    fn assert_problem_eq(expected: &Problem, actual: &Problem) {
+        assert_eq!(expected.input_parameters.m, actual.input_parameters.m);
+        assert_eq!(expected.input_parameters.k, actual.input_parameters.k);
+        assert_eq!(expected.input_parameters.o, actual.input_parameters.o);
+        assert_eq!(expected.input_parameters.b, actual.input_parameters.b);
+        assert_eq!(expected.glob_optima_score, actual.glob_optima_score);
+        assert_eq!(expected.glob_optima_strings, actual.glob_optima_strings);
+        assert_eq!(expected.cliques, actual.cliques);
+    }
+
+    ///A unique temporary path for `tag` so concurrent test runs do not collide.
+    fn temp_path(tag: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("tdmk_test_{}_{}", std::process::id(), tag));
+        path
+    }
+
+    #[test]
+    fn bin_round_trip() {
+        let problem = sample_problem();
+        let path = temp_path("round_trip.bin");
+        write_problem_bin(&problem, &path).unwrap();
+        let read_back = read_problem_from_file_bin(&path).unwrap();
+        assert_problem_eq(&problem, &read_back);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let problem = sample_problem();
+        let path = temp_path("round_trip.json");
+        write_problem_json(&problem, &path).unwrap();
+        let read_back = read_problem_from_file_json(&path).unwrap();
+        assert_problem_eq(&problem, &read_back);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn convert_bin_to_json() {
+        let problem = sample_problem();
+        let bin_path = temp_path("convert_src.bin");
+        let json_path = temp_path("convert_dst.json");
+        write_problem_bin(&problem, &bin_path).unwrap();
+        convert_problem_file(&bin_path, &json_path, ProblemFormat::Bin, ProblemFormat::Json)
+            .unwrap();
+        let read_back = read_problem_from_file_json(&json_path).unwrap();
+        assert_problem_eq(&problem, &read_back);
+        std::fs::remove_file(&bin_path).ok();
+        std::fs::remove_file(&json_path).ok();
+    }
+
+    #[test]
+    fn gzip_round_trip() {
+        let problem = sample_problem();
+        let path = temp_path("round_trip.bin.gz");
+        write_problem_bin(&problem, &path).unwrap();
+        let read_back = read_problem_from_file_bin(&path).unwrap();
+        assert_problem_eq(&problem, &read_back);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn zstd_round_trip() {
+        let problem = sample_problem();
+        let path = temp_path("round_trip.bin.zst");
+        write_problem_bin(&problem, &path).unwrap();
+        let read_back = read_problem_from_file_bin(&path).unwrap();
+        assert_problem_eq(&problem, &read_back);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn pack_unpack_round_trip() {
+        let source = temp_path("pack_src");
+        let output = temp_path("pack_out");
+        let archive = temp_path("pack.tdar");
+        std::fs::remove_dir_all(&source).ok();
+        std::fs::remove_dir_all(&output).ok();
+
+        let nested = source.join("codomain_files").join("sub");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("a.txt"), b"hello").unwrap();
+        std::fs::write(source.join("top.bin"), [0u8, 1, 2]).unwrap();
+
+        pack_folder(&source, &archive).unwrap();
+        unpack_archive(&archive, &output).unwrap();
+
+        assert_eq!(
+            std::fs::read(output.join("codomain_files").join("sub").join("a.txt")).unwrap(),
+            b"hello"
+        );
+        assert_eq!(
+            std::fs::read(output.join("top.bin")).unwrap(),
+            vec![0u8, 1, 2]
+        );
+
+        std::fs::remove_dir_all(&source).ok();
+        std::fs::remove_dir_all(&output).ok();
+        std::fs::remove_file(&archive).ok();
+    }
+
+    #[test]
+    fn unpack_rejects_path_traversal() {
+        let base = std::env::temp_dir();
+        assert!(sanitized_output_path(&base, "../evil").is_err());
+        assert!(sanitized_output_path(&base, "/etc/passwd").is_err());
+        assert!(sanitized_output_path(&base, "ok/sub.txt").is_ok());
+    }
 }
\ No newline at end of file